@@ -161,6 +161,131 @@ async fn test_session_id_generation() {
     assert_ne!(session_id, session_id2);
 }
 
+#[tokio::test]
+async fn test_require_sdp_content_type_accepts_application_sdp() {
+    let mut headers = axum::http::HeaderMap::new();
+    headers.insert(
+        axum::http::header::CONTENT_TYPE,
+        "application/sdp".parse().unwrap(),
+    );
+    assert!(require_sdp_content_type(&headers).is_ok());
+}
+
+#[tokio::test]
+async fn test_require_sdp_content_type_rejects_json() {
+    let mut headers = axum::http::HeaderMap::new();
+    headers.insert(
+        axum::http::header::CONTENT_TYPE,
+        "application/json".parse().unwrap(),
+    );
+    assert!(require_sdp_content_type(&headers).is_err());
+}
+
+#[tokio::test]
+async fn test_whip_resource_location_format() {
+    assert_eq!(
+        whip_resource_location("test-session"),
+        "/whip/resource/test-session"
+    );
+}
+
+#[tokio::test]
+async fn test_whep_resource_location_format() {
+    assert_eq!(
+        whep_resource_location("test-session"),
+        "/whep/resource/test-session"
+    );
+}
+
+#[tokio::test]
+async fn test_whep_query_deserialization_without_session_id() {
+    let query: WhepQuery = serde_json::from_str("{}").unwrap();
+    assert!(query.session_id.is_none());
+}
+
+#[tokio::test]
+async fn test_whep_query_deserialization_with_session_id() {
+    let query: WhepQuery = serde_json::from_str(r#"{"session_id": "pub-123"}"#).unwrap();
+    assert_eq!(query.session_id.as_deref(), Some("pub-123"));
+}
+
+#[tokio::test]
+async fn test_parse_trickle_ice_fragment_single_mid() {
+    let fragment = concat!(
+        "a=ice-ufrag:4ZcD\r\n",
+        "a=ice-pwd:2/1muCWoOi3uHTiCSIWszae17p\r\n",
+        "m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n",
+        "a=mid:0\r\n",
+        "a=candidate:1 1 UDP 2013266431 192.168.1.1 54400 typ host\r\n",
+        "a=candidate:2 1 TCP 1019216383 192.168.1.1 9 typ host tcptype active\r\n",
+    );
+
+    let candidates = parse_trickle_ice_fragment(fragment);
+    assert_eq!(candidates.len(), 2);
+    assert_eq!(candidates[0].sdp_mid.as_deref(), Some("0"));
+    assert_eq!(candidates[0].sdp_m_line_index, None);
+    assert!(candidates[0].candidate.starts_with("candidate:1"));
+    assert_eq!(candidates[1].sdp_mid.as_deref(), Some("0"));
+}
+
+#[tokio::test]
+async fn test_parse_trickle_ice_fragment_multiple_mids() {
+    let fragment = concat!(
+        "m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n",
+        "a=mid:0\r\n",
+        "a=candidate:1 1 UDP 2013266431 192.168.1.1 54400 typ host\r\n",
+        "m=video 9 UDP/TLS/RTP/SAVPF 96\r\n",
+        "a=mid:1\r\n",
+        "a=candidate:2 1 UDP 2013266431 192.168.1.1 54401 typ host\r\n",
+    );
+
+    let candidates = parse_trickle_ice_fragment(fragment);
+    assert_eq!(candidates.len(), 2);
+    assert_eq!(candidates[0].sdp_mid.as_deref(), Some("0"));
+    assert_eq!(candidates[0].sdp_m_line_index, None);
+    assert_eq!(candidates[1].sdp_mid.as_deref(), Some("1"));
+    assert_eq!(candidates[1].sdp_m_line_index, None);
+}
+
+#[tokio::test]
+async fn test_parse_trickle_ice_fragment_mid_not_starting_at_zero() {
+    // A fragment is not required to open on the offer's first m-line; with no
+    // way to recover the true m-line position from the fragment alone, the
+    // index must be left unset rather than guessed from fragment-local order.
+    let fragment = concat!(
+        "m=video 9 UDP/TLS/RTP/SAVPF 96\r\n",
+        "a=mid:1\r\n",
+        "a=candidate:1 1 UDP 2013266431 192.168.1.1 54401 typ host\r\n",
+    );
+
+    let candidates = parse_trickle_ice_fragment(fragment);
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].sdp_mid.as_deref(), Some("1"));
+    assert_eq!(candidates[0].sdp_m_line_index, None);
+}
+
+#[tokio::test]
+async fn test_parse_trickle_ice_fragment_no_candidates() {
+    let fragment = "a=ice-ufrag:4ZcD\r\na=ice-pwd:2/1muCWoOi3uHTiCSIWszae17p\r\n";
+    assert!(parse_trickle_ice_fragment(fragment).is_empty());
+}
+
+#[tokio::test]
+async fn test_mint_turn_credentials_username_format() {
+    let (username, credential) = mint_turn_credentials("shared-secret", 86400, "user-1");
+    let (expiry, user_id) = username.split_once(':').expect("username must be expiry:user_id");
+    assert!(expiry.parse::<u64>().is_ok());
+    assert_eq!(user_id, "user-1");
+    assert!(!credential.is_empty());
+}
+
+#[tokio::test]
+async fn test_mint_turn_credentials_differ_by_secret() {
+    let (_, credential_a) = mint_turn_credentials("secret-a", 86400, "user-1");
+    let (_, credential_b) = mint_turn_credentials("secret-b", 86400, "user-1");
+    assert_ne!(credential_a, credential_b);
+}
+
 // Integration-style test for the complete flow
 #[tokio::test]
 async fn test_complete_sdp_flow_structure() {
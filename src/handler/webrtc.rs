@@ -1,17 +1,30 @@
 use super::middleware::clientaddr::ClientAddr;
 use crate::app::AppState;
+use crate::media::stream::MediaStream;
+use crate::session::{SessionHandle, SessionRole};
 use axum::{
-    extract::State,
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
+use base64::{engine::general_purpose, Engine as _};
+use hmac::{Hmac, Mac};
 use reqwest;
 use serde::{Deserialize, Serialize};
-use std::{env, time::Instant};
+use sha1::Sha1;
+use std::{
+    env,
+    sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
 use tracing::{error, info};
 use uuid::Uuid;
 
+/// Default TTL for ephemeral TURN credentials minted by `generate_turn_ice_servers`,
+/// matching the TURN REST API convention's usual one-day lease.
+const DEFAULT_TURN_CREDENTIAL_TTL_SECS: u64 = 86400;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IceServer {
     urls: Vec<String>,
@@ -123,7 +136,7 @@ pub(crate) async fn handle_sdp_offer(
 }
 
 /// Process SDP offer and create WebRTC track to generate answer
-async fn process_sdp_offer(
+pub(crate) async fn process_sdp_offer(
     state: &AppState,
     offer_sdp: &str,
     session_id: &str,
@@ -133,14 +146,13 @@ async fn process_sdp_offer(
         track::{webrtc::WebrtcTrack, TrackConfig},
     };
     use tokio_util::sync::CancellationToken;
-    use std::sync::Arc;
-    
+
     // Create cancellation token for this session
     let cancel_token = CancellationToken::new();
-    
+
     // Create event sender
     let event_sender = crate::event::create_event_sender();
-    
+
     // Create media stream
     let media_stream_builder = MediaStreamBuilder::new(event_sender.clone());
     let media_stream = Arc::new(
@@ -148,7 +160,7 @@ async fn process_sdp_offer(
             .with_cancel_token(cancel_token.clone())
             .build(),
     );
-    
+
     // Create WebRTC track
     let track_id = format!("webrtc-{}", session_id);
     let mut webrtc_track = WebrtcTrack::new(
@@ -156,13 +168,29 @@ async fn process_sdp_offer(
         track_id.clone(),
         TrackConfig::default(),
     );
-    
+
     // Setup WebRTC track with the offer SDP
     let answer = webrtc_track.setup_webrtc_track(offer_sdp.to_string(), None).await?;
-    
+
+    // Register the session before handing the track's ownership to the media
+    // stream, so trickle ICE candidates and WHEP lookups can reach the same
+    // live peer connection that is about to start serving media.
+    state
+        .session_manager
+        .insert(
+            session_id,
+            SessionHandle::new(
+                SessionRole::Publisher,
+                cancel_token.clone(),
+                media_stream.clone(),
+                webrtc_track.clone(),
+            ),
+        )
+        .await;
+
     // Store the track in media stream for processing
     media_stream.update_track(Box::new(webrtc_track)).await;
-    
+
     // Start media stream in background
     let media_stream_clone = media_stream.clone();
     tokio::spawn(async move {
@@ -170,13 +198,434 @@ async fn process_sdp_offer(
             error!("Media stream error for session {}: {}", session_id, e);
         }
     });
-    
-    // Store session info in app state for later reference
-    // Note: You might want to add a session storage mechanism to AppState
-    
+
     Ok(answer.sdp)
 }
 
+/// Handle a WHIP (WebRTC-HTTP Ingestion Protocol, RFC 9725) offer.
+///
+/// Unlike `handle_sdp_offer`, the body is a raw `application/sdp` document rather
+/// than our bespoke JSON envelope, so standard publishers (OBS, GStreamer's
+/// `whipsink`, browser clients using the WHIP spec directly) can ingest media
+/// without any custom glue. The offer is run through the same
+/// `process_sdp_offer`/`WebrtcTrack::setup_webrtc_track` path as the JSON handler.
+pub(crate) async fn handle_whip_offer(
+    client_ip: ClientAddr,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    offer_sdp: String,
+) -> Response {
+    if let Err(message) = require_sdp_content_type(&headers) {
+        return (StatusCode::UNSUPPORTED_MEDIA_TYPE, message).into_response();
+    }
+
+    if offer_sdp.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, "SDP offer body cannot be empty").into_response();
+    }
+
+    let session_id = Uuid::new_v4().to_string();
+    info!("Received WHIP offer, assigning session: {}", session_id);
+
+    match process_sdp_offer(&state, &offer_sdp, &session_id).await {
+        Ok(answer_sdp) => {
+            info!("Generated WHIP answer for session: {}", session_id);
+            let mut response = (StatusCode::CREATED, answer_sdp).into_response();
+            let headers = response.headers_mut();
+            headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/sdp"),
+            );
+            if let Ok(value) = HeaderValue::from_str(&whip_resource_location(&session_id)) {
+                headers.insert(header::LOCATION, value);
+            }
+            let turn_user_id = format!("{}-{}", client_ip.ip(), session_id);
+            for link in ice_server_link_headers(&state, &turn_user_id) {
+                if let Ok(value) = HeaderValue::from_str(&link) {
+                    headers.append(header::LINK, value);
+                }
+            }
+            response
+        }
+        Err(e) => {
+            error!("Failed to process WHIP offer for session {}: {}", session_id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to process SDP offer: {}", e),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Tear down the WHIP resource created by `handle_whip_offer`. This is the same
+/// teardown as `handle_close_session`, just reached via `DELETE` on the resource
+/// URL that was returned in the `Location` header instead of a JSON body.
+pub(crate) async fn handle_whip_delete(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> Response {
+    info!("Closing WHIP session via resource delete: {}", session_id);
+    close_session(&state, &session_id, Some("WHIP resource deleted")).await;
+    StatusCode::NO_CONTENT.into_response()
+}
+
+/// Build the `Location` header value for a newly created WHIP resource (WHEP
+/// resources use `whep_resource_location`).
+pub(crate) fn whip_resource_location(session_id: &str) -> String {
+    format!("/whip/resource/{}", session_id)
+}
+
+/// Handle an HTTP `PATCH` on a WHIP resource carrying a trickle-ICE SDP
+/// fragment (`Content-Type: application/trickle-ice-sdpfragment`, RFC 8840 /
+/// the WHIP spec). Lets clients send candidates as they're discovered instead
+/// of requiring them bundled in the initial offer, complementing the JSON
+/// `handle_ice_candidate` path for non-WHIP clients.
+pub(crate) async fn handle_whip_patch(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+    fragment: String,
+) -> Response {
+    match headers.get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()) {
+        Some(ct) if ct.starts_with("application/trickle-ice-sdpfragment") => {}
+        _ => {
+            return (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "Content-Type must be application/trickle-ice-sdpfragment",
+            )
+                .into_response();
+        }
+    }
+
+    let Some(handle) = state.session_manager.get(&session_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            format!("Unknown WHIP session: {}", session_id),
+        )
+            .into_response();
+    };
+
+    let candidates = parse_trickle_ice_fragment(&fragment);
+    if candidates.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            "Trickle-ICE fragment did not contain any a=candidate lines",
+        )
+            .into_response();
+    }
+
+    for candidate in candidates {
+        if let Err(e) = handle.webrtc_track.add_ice_candidate(candidate).await {
+            error!(
+                "Failed to add trickle ICE candidate for session {}: {}",
+                session_id, e
+            );
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to add ICE candidate: {}", e),
+            )
+                .into_response();
+        }
+    }
+    handle.touch();
+
+    StatusCode::NO_CONTENT.into_response()
+}
+
+/// Parse a trickle-ICE SDP fragment into `IceCandidate`s, carrying forward the
+/// `a=mid:` context onto each `a=candidate:` line that follows it so the
+/// resulting candidates can be routed to the right m-line.
+///
+/// Only `sdp_mid` is filled in, not `sdp_m_line_index`: a trickle fragment
+/// (RFC 8840) is not required to start at the offer's first m-line, so
+/// counting `a=mid:` lines seen within the fragment itself does not recover
+/// the candidate's real m-line position -- a fragment carrying only a
+/// non-zero section (e.g. just `a=mid:1`) would otherwise be mislabeled as
+/// index 0. `sdp_mid` alone is enough for `WebrtcTrack::add_ice_candidate` to
+/// route the candidate correctly.
+pub(crate) fn parse_trickle_ice_fragment(fragment: &str) -> Vec<IceCandidate> {
+    let mut candidates = Vec::new();
+    let mut current_mid: Option<String> = None;
+
+    for line in fragment.lines() {
+        let line = line.trim();
+        if let Some(mid) = line.strip_prefix("a=mid:") {
+            current_mid = Some(mid.to_string());
+        } else if let Some(candidate) = line.strip_prefix("a=candidate:") {
+            candidates.push(IceCandidate {
+                candidate: format!("candidate:{}", candidate),
+                sdp_mid: current_mid.clone(),
+                sdp_m_line_index: None,
+            });
+        }
+    }
+
+    candidates
+}
+
+/// Validate that a request carries `Content-Type: application/sdp`, as required
+/// by the WHIP/WHEP specs for raw SDP bodies.
+pub(crate) fn require_sdp_content_type(headers: &HeaderMap) -> Result<(), &'static str> {
+    match headers.get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()) {
+        Some(ct) if ct.starts_with("application/sdp") => Ok(()),
+        _ => Err("Content-Type must be application/sdp"),
+    }
+}
+
+/// Render the configured ICE servers as `Link: <uri>; rel="ice-server"` header
+/// values per the WHIP spec, so WHIP/WHEP clients learn STUN/TURN servers
+/// without a separate `/iceservers` round trip. Mints ephemeral TURN
+/// credentials via `generate_turn_ice_servers` when a `turn_secret` is
+/// configured, the same as `get_iceservers`, so WHIP/WHEP clients get relay
+/// access without depending on `config.ice_servers` also being set;
+/// `turn_user_id` binds the minted credential to the request the same way
+/// `get_iceservers` binds it to the caller's IP.
+pub(crate) fn ice_server_link_headers(state: &AppState, turn_user_id: &str) -> Vec<String> {
+    if let Some(turn_servers) = generate_turn_ice_servers(state, turn_user_id) {
+        return ice_servers_to_link_headers(turn_servers);
+    }
+
+    let ice_servers = state
+        .config
+        .ice_servers
+        .clone()
+        .unwrap_or_else(|| vec![IceServer {
+            urls: vec!["stun:restsend.com:3478".to_string()],
+            username: None,
+            credential: None,
+        }]);
+
+    ice_servers_to_link_headers(ice_servers)
+}
+
+/// Render `IceServer`s as `Link: <uri>; rel="ice-server"` header values per
+/// the WHIP spec.
+fn ice_servers_to_link_headers(ice_servers: Vec<IceServer>) -> Vec<String> {
+    ice_servers
+        .into_iter()
+        .flat_map(|server| {
+            server.urls.into_iter().map(move |url| {
+                let mut link = format!("<{}>; rel=\"ice-server\"", url);
+                if let Some(username) = &server.username {
+                    link.push_str(&format!("; username=\"{}\"", username));
+                }
+                if let Some(credential) = &server.credential {
+                    link.push_str(&format!("; credential=\"{}\"; credential-type=\"password\"", credential));
+                }
+                link
+            })
+        })
+        .collect()
+}
+
+/// Query parameters accepted by `handle_whep_offer`.
+#[derive(Debug, Deserialize)]
+pub struct WhepQuery {
+    /// Publisher session to subscribe to. When omitted, an arbitrary active
+    /// publisher is selected, which is only sensible while there is a single
+    /// publisher in flight.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Handle a WHEP (WebRTC-HTTP Egress Protocol) offer, mirroring the WHIP ingest
+/// path for playback. A `recvonly` offer is turned into a sendonly `WebrtcTrack`
+/// that fans out the media already flowing through a publisher's
+/// `MediaStream`, turning the server into a one-to-many SFU rather than only
+/// handling the single offer/answer exchange of `process_sdp_offer`.
+pub(crate) async fn handle_whep_offer(
+    client_ip: ClientAddr,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<WhepQuery>,
+    offer_sdp: String,
+) -> Response {
+    if let Err(message) = require_sdp_content_type(&headers) {
+        return (StatusCode::UNSUPPORTED_MEDIA_TYPE, message).into_response();
+    }
+
+    if offer_sdp.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, "SDP offer body cannot be empty").into_response();
+    }
+
+    let publisher_stream = match state
+        .session_manager
+        .find_publisher_stream(params.session_id.as_deref())
+    {
+        Some(stream) => stream,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                "No active publisher session to subscribe to",
+            )
+                .into_response();
+        }
+    };
+
+    let session_id = Uuid::new_v4().to_string();
+    info!("Received WHEP offer, assigning subscriber session: {}", session_id);
+
+    match process_whep_offer(&state, &offer_sdp, &session_id, publisher_stream).await {
+        Ok(answer_sdp) => {
+            info!("Generated WHEP answer for subscriber session: {}", session_id);
+            let mut response = (StatusCode::CREATED, answer_sdp).into_response();
+            let headers = response.headers_mut();
+            headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/sdp"),
+            );
+            if let Ok(value) = HeaderValue::from_str(&whep_resource_location(&session_id)) {
+                headers.insert(header::LOCATION, value);
+            }
+            let turn_user_id = format!("{}-{}", client_ip.ip(), session_id);
+            for link in ice_server_link_headers(&state, &turn_user_id) {
+                if let Ok(value) = HeaderValue::from_str(&link) {
+                    headers.append(header::LINK, value);
+                }
+            }
+            response
+        }
+        Err(e) => {
+            error!("Failed to process WHEP offer for session {}: {}", session_id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to process SDP offer: {}", e),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Build the `Location` header value for a newly created WHEP subscriber resource.
+pub(crate) fn whep_resource_location(session_id: &str) -> String {
+    format!("/whep/resource/{}", session_id)
+}
+
+/// Set up a sendonly `WebrtcTrack` for a WHEP subscriber and attach it to the
+/// publisher's `MediaStream` as an additional subscriber track, so it
+/// receives the fan-out of published media alongside every other subscriber
+/// without disturbing the publisher's own ingest track. `MediaStream` keeps
+/// its single-track `update_track` setter for the publisher path in
+/// `process_sdp_offer`; subscribers go through `add_subscriber_track`/
+/// `remove_subscriber_track` instead, which is additive rather than
+/// replacing, making one-to-many fan-out actually possible.
+pub(crate) async fn process_whep_offer(
+    state: &AppState,
+    offer_sdp: &str,
+    session_id: &str,
+    publisher_stream: Arc<MediaStream>,
+) -> Result<String, anyhow::Error> {
+    use crate::media::track::{webrtc::WebrtcTrack, TrackConfig};
+    use tokio_util::sync::CancellationToken;
+
+    let cancel_token = CancellationToken::new();
+    let track_id = format!("webrtc-whep-{}", session_id);
+    let mut webrtc_track = WebrtcTrack::new(cancel_token.child_token(), track_id, TrackConfig::default());
+
+    let answer = webrtc_track.setup_webrtc_track(offer_sdp.to_string(), None).await?;
+
+    state
+        .session_manager
+        .insert(
+            session_id,
+            SessionHandle::new(
+                SessionRole::Subscriber,
+                cancel_token,
+                publisher_stream.clone(),
+                webrtc_track.clone(),
+            ),
+        )
+        .await;
+
+    publisher_stream
+        .add_subscriber_track(session_id.to_string(), Box::new(webrtc_track))
+        .await;
+
+    Ok(answer.sdp)
+}
+
+/// Create a sendonly `WebrtcTrack` for a subscriber session the *server*
+/// initiates, rather than one driven by a client-sent WHEP/`Offer` message.
+/// Used by `/ws/signal` to push an offer to a subscribing peer as soon as it
+/// joins a room with an already-live publisher, instead of waiting for the
+/// peer to ask for one. Registration and `add_subscriber_track` happen
+/// immediately, same as `process_whep_offer`; only which side sends the
+/// offer differs. Returns the local offer SDP to forward to the peer, who
+/// completes the handshake via `complete_subscriber_answer`.
+pub(crate) async fn initiate_subscriber_session(
+    state: &AppState,
+    session_id: &str,
+    publisher_stream: Arc<MediaStream>,
+) -> Result<String, anyhow::Error> {
+    use crate::media::track::{webrtc::WebrtcTrack, TrackConfig};
+    use tokio_util::sync::CancellationToken;
+
+    let cancel_token = CancellationToken::new();
+    let track_id = format!("webrtc-whep-{}", session_id);
+    let mut webrtc_track =
+        WebrtcTrack::new(cancel_token.child_token(), track_id, TrackConfig::default());
+
+    let offer = webrtc_track.create_offer().await?;
+
+    state
+        .session_manager
+        .insert(
+            session_id,
+            SessionHandle::new(
+                SessionRole::Subscriber,
+                cancel_token,
+                publisher_stream.clone(),
+                webrtc_track.clone(),
+            ),
+        )
+        .await;
+
+    publisher_stream
+        .add_subscriber_track(session_id.to_string(), Box::new(webrtc_track))
+        .await;
+
+    Ok(offer.sdp)
+}
+
+/// Apply a subscriber peer's answer to the offer `initiate_subscriber_session`
+/// or `renegotiate_subscriber_session` sent it, completing a server-initiated
+/// handshake the same way `setup_webrtc_track` completes a client-initiated
+/// one.
+pub(crate) async fn complete_subscriber_answer(
+    state: &AppState,
+    session_id: &str,
+    answer_sdp: &str,
+) -> Result<(), anyhow::Error> {
+    let handle = state
+        .session_manager
+        .get(session_id)
+        .ok_or_else(|| anyhow::anyhow!("no session awaiting an answer: {}", session_id))?;
+    handle
+        .webrtc_track
+        .set_remote_answer(answer_sdp.to_string())
+        .await?;
+    handle.touch();
+    Ok(())
+}
+
+/// Re-offer an already-connected subscriber session's existing `WebrtcTrack`,
+/// reflecting whatever tracks its publisher's `MediaStream` now carries.
+/// Used to push a fresh offer when a room's publisher starts, stops, or
+/// renegotiates, rather than leaving a subscriber's peer connection stuck
+/// with the track set that existed when it first joined.
+pub(crate) async fn renegotiate_subscriber_session(
+    state: &AppState,
+    session_id: &str,
+) -> Result<String, anyhow::Error> {
+    let handle = state
+        .session_manager
+        .get(session_id)
+        .filter(|handle| handle.role == SessionRole::Subscriber)
+        .ok_or_else(|| anyhow::anyhow!("no subscriber session to renegotiate: {}", session_id))?;
+    let offer = handle.webrtc_track.create_offer().await?;
+    Ok(offer.sdp)
+}
+
 /// Handle ICE candidate exchange
 #[derive(Debug, Deserialize)]
 pub struct IceCandidateRequest {
@@ -191,22 +640,41 @@ pub struct IceCandidateResponse {
 }
 
 pub(crate) async fn handle_ice_candidate(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Json(request): Json<IceCandidateRequest>,
 ) -> Response {
     info!("Received ICE candidate for session: {}", request.session_id);
-    
-    // In a real implementation, you would:
-    // 1. Find the WebRTC peer connection for this session
-    // 2. Add the ICE candidate to the peer connection
-    // 3. Handle any errors
-    
-    // For now, we'll just acknowledge receipt
+
+    let Some(handle) = state.session_manager.get(&request.session_id) else {
+        let error = ErrorResponse {
+            error: format!("Unknown session: {}", request.session_id),
+            code: 404,
+            session_id: Some(request.session_id),
+        };
+        return (StatusCode::NOT_FOUND, Json(error)).into_response();
+    };
+
+    // Forward the candidate into the live peer connection so trickle ICE
+    // works mid-call, not just for candidates bundled in the initial offer.
+    if let Err(e) = handle.webrtc_track.add_ice_candidate(request.candidate).await {
+        error!(
+            "Failed to add ICE candidate for session {}: {}",
+            request.session_id, e
+        );
+        let error = ErrorResponse {
+            error: format!("Failed to add ICE candidate: {}", e),
+            code: 500,
+            session_id: Some(request.session_id),
+        };
+        return (StatusCode::INTERNAL_SERVER_ERROR, Json(error)).into_response();
+    }
+    handle.touch();
+
     let response = IceCandidateResponse {
         session_id: request.session_id,
         status: "received".to_string(),
     };
-    
+
     (StatusCode::OK, Json(response)).into_response()
 }
 
@@ -225,31 +693,72 @@ pub struct CloseSessionResponse {
 }
 
 pub(crate) async fn handle_close_session(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Json(request): Json<CloseSessionRequest>,
 ) -> Response {
-    info!("Closing WebRTC session: {} (reason: {:?})", 
-          request.session_id, request.reason);
-    
-    // In a real implementation, you would:
-    // 1. Find the session and associated resources
-    // 2. Cancel any running tasks
-    // 3. Close peer connection
-    // 4. Clean up media streams
-    
+    close_session(&state, &request.session_id, request.reason.as_deref()).await;
+
     let response = CloseSessionResponse {
         session_id: request.session_id,
         status: "closed".to_string(),
     };
-    
+
     (StatusCode::OK, Json(response)).into_response()
 }
 
+/// Tear down a WebRTC session. Shared by the JSON `handle_close_session` path and
+/// the WHIP `DELETE` resource path: cancel the session's token so its media
+/// stream and peer connection unwind, then drop it from the registry. A WHEP
+/// subscriber's track is also detached from the publisher's `MediaStream` it
+/// was fanned out on, so a departed viewer doesn't linger as a dead
+/// subscriber track. Closing a publisher instead tears down every subscriber
+/// fanned out onto its stream, so they don't linger pointing at a cancelled
+/// stream until `SessionManager::gc_expired` eventually reaps them.
+pub(crate) async fn close_session(state: &AppState, session_id: &str, reason: Option<&str>) {
+    info!("Closing WebRTC session: {} (reason: {:?})", session_id, reason);
+
+    match state.session_manager.remove(session_id) {
+        Some(handle) => {
+            if handle.role == SessionRole::Subscriber {
+                handle.media_stream.remove_subscriber_track(session_id).await;
+            } else {
+                for (subscriber_id, subscriber_handle) in
+                    state.session_manager.remove_subscribers_of(&handle.media_stream)
+                {
+                    subscriber_handle
+                        .media_stream
+                        .remove_subscriber_track(&subscriber_id)
+                        .await;
+                    subscriber_handle.cancel_token.cancel();
+                }
+            }
+            handle.cancel_token.cancel();
+        }
+        None => error!("Tried to close unknown WebRTC session: {}", session_id),
+    }
+}
+
 // Keep the existing ICE server function
 pub(crate) async fn get_iceservers(
     client_ip: ClientAddr,
     State(state): State<AppState>,
 ) -> Response {
+    // Mint local, time-limited TURN credentials when a shared secret is
+    // configured, removing the hard dependency on restsend.com for relay access.
+    // Bind each credential to this caller rather than issuing the same
+    // "<expiry>:" username/credential pair to everyone: the client's IP
+    // anchors it to a caller for auditing, and the nonce guarantees
+    // uniqueness per request so a leaked credential is scoped narrowly
+    // instead of being usable by every client until expiry.
+    let turn_user_id = format!("{}-{}", client_ip.ip(), Uuid::new_v4());
+    if let Some(turn_servers) = generate_turn_ice_servers(&state, &turn_user_id) {
+        info!(
+            "voiceserver: minted ephemeral TURN credentials for clientIP: {}",
+            client_ip
+        );
+        return Json(turn_servers).into_response();
+    }
+
     let rs_token = env::var("RESTSEND_TOKEN").unwrap_or_default();
     let default_ice_servers = state.config.ice_servers.as_ref();
     if rs_token.is_empty() {
@@ -317,3 +826,55 @@ pub(crate) async fn get_iceservers(
         }
     }
 }
+
+/// Build TURN `IceServer`s with ephemeral credentials when a `turn_secret` and
+/// at least one `turn:`/`turns:` URI are configured, `None` otherwise so the
+/// caller can fall back to the existing STUN-only default. `user_id` binds
+/// the minted credential to the caller that requested it.
+fn generate_turn_ice_servers(state: &AppState, user_id: &str) -> Option<Vec<IceServer>> {
+    let secret = state.config.turn_secret.as_ref()?;
+    if state.config.turn_urls.is_empty() {
+        return None;
+    }
+
+    let ttl = state
+        .config
+        .turn_credential_ttl_secs
+        .unwrap_or(DEFAULT_TURN_CREDENTIAL_TTL_SECS);
+    let (username, credential) = mint_turn_credentials(secret, ttl, user_id);
+
+    Some(
+        state
+            .config
+            .turn_urls
+            .iter()
+            .cloned()
+            .map(|url| IceServer {
+                urls: vec![url],
+                username: Some(username.clone()),
+                credential: Some(credential.clone()),
+            })
+            .collect(),
+    )
+}
+
+/// Mint ephemeral TURN credentials per the TURN REST API convention coturn
+/// implements: `username = "<unix_expiry>:<user_id>"`, `credential =
+/// base64(HMAC-SHA1(secret, username))`. The TURN server validates a request
+/// by recomputing the same HMAC and checking the timestamp hasn't expired, so
+/// issuing credentials needs no round trip to an external provider.
+pub(crate) fn mint_turn_credentials(secret: &str, ttl_secs: u64, user_id: &str) -> (String, String) {
+    let expiry = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        + ttl_secs;
+    let username = format!("{}:{}", expiry, user_id);
+
+    type HmacSha1 = Hmac<Sha1>;
+    let mut mac = HmacSha1::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(username.as_bytes());
+    let credential = general_purpose::STANDARD.encode(mac.finalize().into_bytes());
+
+    (username, credential)
+}
@@ -0,0 +1,658 @@
+use super::webrtc::{
+    close_session, complete_subscriber_answer, initiate_subscriber_session, process_sdp_offer,
+    process_whep_offer, renegotiate_subscriber_session, IceCandidate,
+};
+use crate::app::AppState;
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+};
+use dashmap::DashMap;
+use jsonwebtoken::{decode, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Claims carried by the JWT a signaling client presents in its `join`
+/// message, granting publish and/or subscribe rights in a room. Signed with
+/// the configured API key/secret, same as the rest of the crate's auth.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(test, derive(Serialize))]
+struct SignalClaims {
+    room: String,
+    #[serde(default)]
+    can_publish: bool,
+    #[serde(default)]
+    can_subscribe: bool,
+    // Read by jsonwebtoken's default `Validation` during `decode`, not by us directly.
+    #[allow(dead_code)]
+    exp: usize,
+}
+
+/// Messages a client sends over the `/ws/signal` socket. `Offer` is still
+/// accepted from a subscribing peer as a manual fallback (e.g. re-subscribing
+/// after a lost connection), but the normal subscriber path never sends one:
+/// the server pushes a `ServerMessage::Offer` on its own and the peer only
+/// ever answers it with `Answer`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Join { room: String, token: String },
+    Offer { sdp: String },
+    Answer { sdp: String },
+    Candidate { candidate: IceCandidate },
+    Renegotiate { sdp: String },
+    Leave,
+}
+
+/// Messages the server sends over the `/ws/signal` socket. `Offer` is
+/// server-initiated: pushed to a subscriber when it joins a room that
+/// already has a publisher, and again whenever that room's publisher set
+/// changes, rather than requiring the subscriber to ask.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    Joined { peer_id: String, room: String },
+    Offer { sdp: String },
+    Answer { sdp: String },
+    Candidate { candidate: IceCandidate },
+    Error { message: String },
+}
+
+/// A push asking a peer's own connection task to renegotiate, e.g. because
+/// its room's publisher just started, stopped, or changed track. Carries no
+/// SDP: only the peer's own task holds the live `WebSocket` to send the
+/// resulting offer on, so it generates the offer itself via
+/// `renegotiate_subscriber_session` upon receiving this.
+#[derive(Debug, Clone, Copy)]
+enum PeerSignal {
+    Renegotiate,
+}
+
+/// Registry of connected `/ws/signal` peers' push channels, keyed by peer id
+/// and tagged with the room each one joined. This is what makes
+/// server-initiated offers possible: a publisher's connection task has no
+/// direct handle to a subscriber's `WebSocket` (it's owned by that
+/// subscriber's own task), so it reaches it indirectly by sending a
+/// `PeerSignal` through this registry instead. `AppState` owns one instance,
+/// shared across every signaling socket.
+#[derive(Clone, Default)]
+pub struct SignalPeers {
+    peers: Arc<DashMap<String, (String, mpsc::UnboundedSender<PeerSignal>)>>,
+}
+
+impl SignalPeers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn register(&self, peer_id: String, room: String, sender: mpsc::UnboundedSender<PeerSignal>) {
+        self.peers.insert(peer_id, (room, sender));
+    }
+
+    fn unregister(&self, peer_id: &str) {
+        self.peers.remove(peer_id);
+    }
+
+    /// Ask every other peer currently in `room` to renegotiate. Best-effort:
+    /// a peer whose task has already moved on to disconnecting is simply
+    /// skipped rather than treated as an error.
+    fn signal_room(&self, room: &str, except_peer_id: &str, signal: PeerSignal) {
+        for entry in self.peers.iter() {
+            let (peer_id, (peer_room, sender)) = (entry.key(), entry.value());
+            if peer_room == room && peer_id != except_peer_id {
+                let _ = sender.send(signal);
+            }
+        }
+    }
+}
+
+/// Upgrade `GET /ws/signal` to a persistent WebSocket signaling connection.
+/// Carries the same publish (WHIP-equivalent) and subscribe (WHEP-equivalent)
+/// offer/answer exchanges as the HTTP endpoints over one long-lived socket per
+/// room, plus trickle ICE and renegotiation without a new HTTP round trip
+/// each time. Unlike the HTTP handlers, a subscriber does not have to send an
+/// offer to start playback: joining a room that already has a publisher gets
+/// it an unsolicited `Offer` right away, and it gets another whenever that
+/// room's publisher starts, stops, or renegotiates, via `SignalPeers`.
+pub(crate) async fn handle_ws_signal(
+    State(state): State<AppState>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    ws.on_upgrade(move |socket| handle_signal_socket(socket, state))
+}
+
+async fn handle_signal_socket(mut socket: WebSocket, state: AppState) {
+    let peer_id = Uuid::new_v4().to_string();
+
+    // The first message on a fresh socket must be `join`, carrying the room
+    // name and an auth token; everything else needs a peer already bound to
+    // a room.
+    let claims = match wait_for_join(&mut socket, &state).await {
+        Ok(claims) => claims,
+        Err(message) => {
+            let _ = send_message(&mut socket, &ServerMessage::Error { message }).await;
+            return;
+        }
+    };
+
+    info!(
+        "WS signal: peer {} joined room {} (publish={}, subscribe={})",
+        peer_id, claims.room, claims.can_publish, claims.can_subscribe
+    );
+    if send_message(
+        &mut socket,
+        &ServerMessage::Joined {
+            peer_id: peer_id.clone(),
+            room: claims.room.clone(),
+        },
+    )
+    .await
+    .is_err()
+    {
+        return;
+    }
+
+    let (signal_tx, mut signal_rx) = mpsc::unbounded_channel::<PeerSignal>();
+    state
+        .signal_peers
+        .register(peer_id.clone(), claims.room.clone(), signal_tx);
+
+    // A publisher elsewhere in the room may already be live: push this
+    // subscriber an offer immediately instead of leaving it to ask for one.
+    if claims.can_subscribe {
+        if let Some(publisher_stream) = state.session_manager.find_publisher_stream_in_room(&claims.room) {
+            match initiate_subscriber_session(&state, &peer_id, publisher_stream).await {
+                Ok(offer_sdp) => {
+                    state.session_manager.set_room(&peer_id, claims.room.clone());
+                    if send_message(&mut socket, &ServerMessage::Offer { sdp: offer_sdp })
+                        .await
+                        .is_err()
+                    {
+                        state.signal_peers.unregister(&peer_id);
+                        return;
+                    }
+                }
+                Err(e) => {
+                    error!("WS signal: peer {} auto-subscribe failed: {}", peer_id, e);
+                    let _ = send_message(
+                        &mut socket,
+                        &ServerMessage::Error {
+                            message: format!("Failed to subscribe: {}", e),
+                        },
+                    )
+                    .await;
+                }
+            }
+        }
+    }
+
+    let mut is_publisher = false;
+
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => {
+                let Some(Ok(message)) = incoming else { break };
+                let Message::Text(text) = message else { continue };
+
+                let client_message: ClientMessage = match serde_json::from_str(&text) {
+                    Ok(message) => message,
+                    Err(e) => {
+                        warn!("WS signal: peer {} sent unparseable message: {}", peer_id, e);
+                        continue;
+                    }
+                };
+
+                match client_message {
+                    ClientMessage::Offer { sdp } => {
+                        if claims.can_publish {
+                            match process_sdp_offer(&state, &sdp, &peer_id).await {
+                                Ok(answer_sdp) => {
+                                    state.session_manager.set_room(&peer_id, claims.room.clone());
+                                    is_publisher = true;
+                                    if send_message(&mut socket, &ServerMessage::Answer { sdp: answer_sdp })
+                                        .await
+                                        .is_err()
+                                    {
+                                        break;
+                                    }
+                                    state.signal_peers.signal_room(&claims.room, &peer_id, PeerSignal::Renegotiate);
+                                }
+                                Err(e) => {
+                                    error!("WS signal: peer {} offer failed: {}", peer_id, e);
+                                    let _ = send_message(
+                                        &mut socket,
+                                        &ServerMessage::Error {
+                                            message: format!("Failed to process SDP offer: {}", e),
+                                        },
+                                    )
+                                    .await;
+                                }
+                            }
+                        } else if claims.can_subscribe {
+                            // Manual fallback for a subscriber that wants to
+                            // (re-)subscribe itself, e.g. after reconnecting.
+                            // The normal path is the automatic push above and
+                            // on `PeerSignal::Renegotiate`.
+                            let publisher_stream =
+                                state.session_manager.find_publisher_stream_in_room(&claims.room);
+                            match publisher_stream {
+                                Some(publisher_stream) => {
+                                    match process_whep_offer(&state, &sdp, &peer_id, publisher_stream).await
+                                    {
+                                        Ok(answer_sdp) => {
+                                            state
+                                                .session_manager
+                                                .set_room(&peer_id, claims.room.clone());
+                                            if send_message(
+                                                &mut socket,
+                                                &ServerMessage::Answer { sdp: answer_sdp },
+                                            )
+                                            .await
+                                            .is_err()
+                                            {
+                                                break;
+                                            }
+                                        }
+                                        Err(e) => {
+                                            error!("WS signal: peer {} subscribe failed: {}", peer_id, e);
+                                            let _ = send_message(
+                                                &mut socket,
+                                                &ServerMessage::Error {
+                                                    message: format!("Failed to subscribe: {}", e),
+                                                },
+                                            )
+                                            .await;
+                                        }
+                                    }
+                                }
+                                None => {
+                                    let _ = send_message(
+                                        &mut socket,
+                                        &ServerMessage::Error {
+                                            message: format!(
+                                                "No active publisher in room {}",
+                                                claims.room
+                                            ),
+                                        },
+                                    )
+                                    .await;
+                                }
+                            }
+                        } else {
+                            let _ = send_message(
+                                &mut socket,
+                                &ServerMessage::Error {
+                                    message: "peer is not authorized to publish or subscribe".to_string(),
+                                },
+                            )
+                            .await;
+                        }
+                    }
+                    ClientMessage::Answer { sdp } => {
+                        if !claims.can_subscribe {
+                            let _ = send_message(
+                                &mut socket,
+                                &ServerMessage::Error {
+                                    message: "peer is not authorized to subscribe".to_string(),
+                                },
+                            )
+                            .await;
+                            continue;
+                        }
+                        if let Err(e) = complete_subscriber_answer(&state, &peer_id, &sdp).await {
+                            error!("WS signal: peer {} answer failed: {}", peer_id, e);
+                            let _ = send_message(
+                                &mut socket,
+                                &ServerMessage::Error {
+                                    message: format!("Failed to apply answer: {}", e),
+                                },
+                            )
+                            .await;
+                        }
+                    }
+                    ClientMessage::Candidate { candidate } => {
+                        match state.session_manager.get(&peer_id) {
+                            Some(handle) => {
+                                if let Err(e) = handle.webrtc_track.add_ice_candidate(candidate).await {
+                                    error!("WS signal: peer {} candidate failed: {}", peer_id, e);
+                                } else {
+                                    handle.touch();
+                                }
+                            }
+                            None => warn!("WS signal: peer {} sent candidate before a session exists", peer_id),
+                        }
+                    }
+                    ClientMessage::Renegotiate { sdp } => {
+                        if !claims.can_publish {
+                            let _ = send_message(
+                                &mut socket,
+                                &ServerMessage::Error {
+                                    message: "peer is not authorized to publish".to_string(),
+                                },
+                            )
+                            .await;
+                            continue;
+                        }
+
+                        // Renegotiate against the peer's existing session the same
+                        // way an initial offer is processed; the answer is pushed
+                        // back the same way. SessionManager::insert cancels the
+                        // session it replaces, so the prior peer connection is torn
+                        // down rather than leaked.
+                        match process_sdp_offer(&state, &sdp, &peer_id).await {
+                            Ok(answer_sdp) => {
+                                state.session_manager.set_room(&peer_id, claims.room.clone());
+                                is_publisher = true;
+                                if send_message(&mut socket, &ServerMessage::Answer { sdp: answer_sdp })
+                                    .await
+                                    .is_err()
+                                {
+                                    break;
+                                }
+                                state.signal_peers.signal_room(&claims.room, &peer_id, PeerSignal::Renegotiate);
+                            }
+                            Err(e) => {
+                                error!("WS signal: peer {} renegotiation failed: {}", peer_id, e);
+                                let _ = send_message(
+                                    &mut socket,
+                                    &ServerMessage::Error {
+                                        message: format!("Failed to renegotiate: {}", e),
+                                    },
+                                )
+                                .await;
+                            }
+                        }
+                    }
+                    ClientMessage::Leave => break,
+                    ClientMessage::Join { .. } => {
+                        warn!("WS signal: peer {} sent a second join, ignoring", peer_id);
+                    }
+                }
+            }
+            Some(signal) = signal_rx.recv() => {
+                match signal {
+                    PeerSignal::Renegotiate => {
+                        if let Some(offer_sdp) =
+                            renegotiate_or_subscribe(&state, &peer_id, &claims.room, claims.can_subscribe).await
+                        {
+                            if send_message(&mut socket, &ServerMessage::Offer { sdp: offer_sdp })
+                                .await
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    state.signal_peers.unregister(&peer_id);
+    if is_publisher {
+        // Let this room's subscribers renegotiate away the track that is
+        // about to disappear, rather than leaving their peer connections
+        // stuck offering a now-dead track.
+        state.signal_peers.signal_room(&claims.room, &peer_id, PeerSignal::Renegotiate);
+    }
+
+    // A subscribe-only peer that joined a room with no live publisher, and
+    // never manually sent an `Offer`, never got a `SessionHandle` created --
+    // e.g. a viewer that joins an empty room and immediately leaves. Don't
+    // route that ordinary case through `close_session`, whose `None` branch
+    // logs at error level for what is normally an unknown-session bug.
+    if state.session_manager.get(&peer_id).is_some() {
+        info!("WS signal: peer {} disconnected, closing session", peer_id);
+        close_session(&state, &peer_id, Some("WebSocket signaling peer disconnected")).await;
+    } else {
+        info!(
+            "WS signal: peer {} disconnected without ever creating a session",
+            peer_id
+        );
+    }
+}
+
+/// Handle a `PeerSignal::Renegotiate` push for `peer_id`: push a fresh offer
+/// to its existing subscriber session, reflecting whatever the room's
+/// publisher now carries. Falls back to subscribing it for the first time if
+/// it never had a subscriber session -- reachable for a peer that joined the
+/// room before any publisher did, so the join-time auto-subscribe in
+/// `handle_signal_socket` never fired for it, and it's only now, on the
+/// publisher's first offer, that there's a stream to attach it to. Returns
+/// the offer SDP to forward to the peer, or `None` if there's nothing to
+/// offer it (not a subscriber, or still no publisher in the room).
+async fn renegotiate_or_subscribe(
+    state: &AppState,
+    peer_id: &str,
+    room: &str,
+    can_subscribe: bool,
+) -> Option<String> {
+    match renegotiate_subscriber_session(state, peer_id).await {
+        Ok(offer_sdp) => Some(offer_sdp),
+        Err(e) => {
+            let publisher_stream =
+                can_subscribe.then(|| state.session_manager.find_publisher_stream_in_room(room)).flatten();
+            let Some(publisher_stream) = publisher_stream else {
+                // Not subscribed (yet) with no publisher to subscribe to, or
+                // no longer a live session -- nothing to renegotiate.
+                warn!("WS signal: peer {} renegotiation push skipped: {}", peer_id, e);
+                return None;
+            };
+            match initiate_subscriber_session(state, peer_id, publisher_stream).await {
+                Ok(offer_sdp) => {
+                    state.session_manager.set_room(peer_id, room.to_string());
+                    Some(offer_sdp)
+                }
+                Err(e) => {
+                    error!("WS signal: peer {} late subscribe failed: {}", peer_id, e);
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Wait for and validate the mandatory first `join` message on a fresh
+/// signaling socket, returning the claims its JWT granted.
+async fn wait_for_join(socket: &mut WebSocket, state: &AppState) -> Result<SignalClaims, String> {
+    let message = socket
+        .recv()
+        .await
+        .ok_or_else(|| "connection closed before join".to_string())?
+        .map_err(|e| format!("failed to read join message: {}", e))?;
+
+    let Message::Text(text) = message else {
+        return Err("first message must be a text `join` frame".to_string());
+    };
+
+    let client_message: ClientMessage =
+        serde_json::from_str(&text).map_err(|e| format!("invalid join message: {}", e))?;
+
+    let ClientMessage::Join { room, token } = client_message else {
+        return Err("first message must be `join`".to_string());
+    };
+
+    let claims = decode_signal_token(state, &token)?;
+    if claims.room != room {
+        return Err("join room does not match the room granted by the token".to_string());
+    }
+
+    Ok(claims)
+}
+
+/// Validate a signaling JWT against the configured API secret and return its
+/// claims.
+fn decode_signal_token(state: &AppState, token: &str) -> Result<SignalClaims, String> {
+    let secret = state
+        .config
+        .ws_signal_secret
+        .as_ref()
+        .ok_or_else(|| "WebSocket signaling is not configured with an API secret".to_string())?;
+
+    decode::<SignalClaims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &Validation::default(),
+    )
+    .map(|data| data.claims)
+    .map_err(|e| format!("invalid or expired signaling token: {}", e))
+}
+
+async fn send_message(socket: &mut WebSocket, message: &ServerMessage) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(message).expect("ServerMessage always serializes");
+    socket.send(Message::Text(text)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{SessionHandle, SessionRole};
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn client_message_tags_parse_by_type() {
+        let join: ClientMessage = serde_json::from_str(
+            r#"{"type": "join", "room": "room-1", "token": "tok"}"#,
+        )
+        .unwrap();
+        assert!(matches!(join, ClientMessage::Join { room, .. } if room == "room-1"));
+
+        let answer: ClientMessage =
+            serde_json::from_str(r#"{"type": "answer", "sdp": "v=0"}"#).unwrap();
+        assert!(matches!(answer, ClientMessage::Answer { sdp } if sdp == "v=0"));
+
+        let leave: ClientMessage = serde_json::from_str(r#"{"type": "leave"}"#).unwrap();
+        assert!(matches!(leave, ClientMessage::Leave));
+    }
+
+    #[test]
+    fn server_message_offer_serializes_with_snake_case_tag() {
+        let message = ServerMessage::Offer { sdp: "v=0".to_string() };
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(json.contains(r#""type":"offer""#));
+        assert!(json.contains(r#""sdp":"v=0""#));
+    }
+
+    fn signed_token(secret: &str, room: &str, can_publish: bool, can_subscribe: bool) -> String {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as usize;
+        let claims = SignalClaims {
+            room: room.to_string(),
+            can_publish,
+            can_subscribe,
+            exp: now + 3600,
+        };
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes())).unwrap()
+    }
+
+    fn app_state_with_secret(secret: Option<&str>) -> AppState {
+        let mut state = AppState::default();
+        state.config.ws_signal_secret = secret.map(|s| s.to_string());
+        state
+    }
+
+    #[test]
+    fn decode_signal_token_rejects_when_unconfigured() {
+        let state = app_state_with_secret(None);
+        let token = signed_token("shared-secret", "room-1", true, false);
+        assert!(decode_signal_token(&state, &token).is_err());
+    }
+
+    #[test]
+    fn decode_signal_token_round_trips_claims() {
+        let state = app_state_with_secret(Some("shared-secret"));
+        let token = signed_token("shared-secret", "room-1", true, false);
+
+        let claims = decode_signal_token(&state, &token).expect("token should decode");
+        assert_eq!(claims.room, "room-1");
+        assert!(claims.can_publish);
+        assert!(!claims.can_subscribe);
+    }
+
+    #[test]
+    fn decode_signal_token_rejects_wrong_secret() {
+        let state = app_state_with_secret(Some("shared-secret"));
+        let token = signed_token("a-different-secret", "room-1", true, false);
+        assert!(decode_signal_token(&state, &token).is_err());
+    }
+
+    #[tokio::test]
+    async fn signal_room_reaches_other_peers_but_not_the_sender_or_other_rooms() {
+        let peers = SignalPeers::new();
+        let (tx_a, mut rx_a) = mpsc::unbounded_channel();
+        let (tx_b, mut rx_b) = mpsc::unbounded_channel();
+        let (tx_other_room, mut rx_other_room) = mpsc::unbounded_channel();
+
+        peers.register("peer-a".to_string(), "room-1".to_string(), tx_a);
+        peers.register("peer-b".to_string(), "room-1".to_string(), tx_b);
+        peers.register("peer-c".to_string(), "room-2".to_string(), tx_other_room);
+
+        peers.signal_room("room-1", "peer-a", PeerSignal::Renegotiate);
+
+        assert!(matches!(rx_b.recv().await, Some(PeerSignal::Renegotiate)));
+        assert!(rx_a.try_recv().is_err());
+        assert!(rx_other_room.try_recv().is_err());
+    }
+
+    #[test]
+    fn unregister_stops_further_signals() {
+        let peers = SignalPeers::new();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        peers.register("peer-a".to_string(), "room-1".to_string(), tx);
+        peers.unregister("peer-a");
+
+        peers.signal_room("room-1", "someone-else", PeerSignal::Renegotiate);
+        assert!(rx.try_recv().is_err());
+    }
+
+    fn test_publisher_handle(media_stream: Arc<crate::media::stream::MediaStream>) -> SessionHandle {
+        use crate::media::track::{webrtc::WebrtcTrack, TrackConfig};
+        use tokio_util::sync::CancellationToken;
+
+        let cancel_token = CancellationToken::new();
+        let webrtc_track =
+            WebrtcTrack::new(cancel_token.child_token(), "test-track".to_string(), TrackConfig::default());
+        SessionHandle::new(SessionRole::Publisher, cancel_token, media_stream, webrtc_track)
+    }
+
+    #[tokio::test]
+    async fn renegotiate_or_subscribe_subscribes_a_peer_that_joined_before_the_publisher() {
+        use crate::event::create_event_sender;
+        use crate::media::stream::MediaStreamBuilder;
+
+        let state = AppState::default();
+
+        // The viewer joined an empty room, so the join-time auto-subscribe in
+        // `handle_signal_socket` never fired for it and it has no session at
+        // all yet.
+        assert!(state.session_manager.get("viewer-1").is_none());
+        assert!(renegotiate_or_subscribe(&state, "viewer-1", "room-1", true).await.is_none());
+
+        // The room's publisher joins afterwards.
+        let publisher_stream = Arc::new(MediaStreamBuilder::new(create_event_sender()).build());
+        state
+            .session_manager
+            .insert("publisher-1", test_publisher_handle(publisher_stream))
+            .await;
+        state.session_manager.set_room("publisher-1", "room-1".to_string());
+
+        let offer = renegotiate_or_subscribe(&state, "viewer-1", "room-1", true).await;
+        assert!(offer.is_some(), "a late publisher should subscribe the waiting viewer");
+        assert!(state.session_manager.get("viewer-1").is_some());
+    }
+
+    #[tokio::test]
+    async fn renegotiate_or_subscribe_skips_a_peer_without_subscribe_rights() {
+        let state = AppState::default();
+        assert!(renegotiate_or_subscribe(&state, "peer-1", "room-1", false).await.is_none());
+    }
+}
@@ -0,0 +1,442 @@
+use crate::media::{stream::MediaStream, track::webrtc::WebrtcTrack};
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+/// How long a session may go without *signaling* activity before
+/// `SessionManager::gc_expired` reaps it. Chosen generously since legitimate
+/// calls can be long-lived; this is a backstop against clients that vanish
+/// without sending `DELETE`/`close`.
+///
+/// This tracks `SessionHandle::last_activity`, bumped via `touch()` on ICE
+/// candidates and renegotiation -- not media packets flowing through the
+/// `MediaStream`, which nothing here observes. A call that streams steadily
+/// but never sends another trickle candidate or renegotiates still goes idle
+/// by this measure and is reaped at the TTL. Raise `SESSION_IDLE_TTL` if that
+/// turns out to cut off real long-lived calls in practice.
+const SESSION_IDLE_TTL: Duration = Duration::from_secs(4 * 3600);
+
+/// How the GC sweep is scheduled when a session manager is spawned by an
+/// `AppState`.
+const GC_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Whether a session is publishing media into the room or only subscribing to
+/// an existing publisher's stream. `SessionManager::find_publisher_stream`
+/// uses this to avoid wiring a WHEP viewer up to another viewer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionRole {
+    Publisher,
+    Subscriber,
+}
+
+fn unix_now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Everything needed to manage one in-flight WebRTC session: the token that
+/// tears it down, the media stream carrying its tracks, and the WebRTC track
+/// itself so trickle ICE candidates can be forwarded into the live peer
+/// connection instead of only being acknowledged.
+#[derive(Clone)]
+pub struct SessionHandle {
+    pub role: SessionRole,
+    pub cancel_token: CancellationToken,
+    pub media_stream: Arc<MediaStream>,
+    pub webrtc_track: WebrtcTrack,
+    // Only set for sessions created over `/ws/signal`, where a room scopes
+    // which publisher a subscriber should be wired up to. WHIP/WHEP/JSON
+    // sessions have no such concept and leave this `None`.
+    room: Option<String>,
+    // Shared across clones so `touch()` on any handle obtained via
+    // `SessionManager::get` updates the same clock the GC sweep reads.
+    last_activity: Arc<AtomicU64>,
+}
+
+impl SessionHandle {
+    pub fn new(
+        role: SessionRole,
+        cancel_token: CancellationToken,
+        media_stream: Arc<MediaStream>,
+        webrtc_track: WebrtcTrack,
+    ) -> Self {
+        Self {
+            role,
+            cancel_token,
+            media_stream,
+            webrtc_track,
+            room: None,
+            last_activity: Arc::new(AtomicU64::new(unix_now_secs())),
+        }
+    }
+
+    /// Record that the session just had signaling activity (ICE candidate
+    /// added, renegotiated, etc.) so idle eviction measures time since last
+    /// signaling use rather than time since creation. Does not reflect
+    /// whether media is still flowing through `media_stream`.
+    pub fn touch(&self) {
+        self.last_activity.store(unix_now_secs(), Ordering::Relaxed);
+    }
+
+    fn idle_for(&self) -> Duration {
+        let last = self.last_activity.load(Ordering::Relaxed);
+        Duration::from_secs(unix_now_secs().saturating_sub(last))
+    }
+}
+
+/// Tracks every active WebRTC session (JSON offer/answer, WHIP, WHEP alike)
+/// so handlers that run after session creation — trickle ICE, close, WHEP
+/// subscription lookup — can reach back into the live peer connection rather
+/// than only acknowledging requests.
+#[derive(Clone, Default)]
+pub struct SessionManager {
+    sessions: Arc<DashMap<String, SessionHandle>>,
+}
+
+impl SessionManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a session, cancelling whatever session previously occupied
+    /// the same id instead of silently dropping it. Without this, replacing a
+    /// peer's session (e.g. renegotiation re-running the offer/answer flow)
+    /// would leak the old `CancellationToken`'s media stream and peer
+    /// connection, since nothing else still held a reference to cancel it.
+    ///
+    /// If the replaced session was a subscriber, its track is also detached
+    /// from the publisher `MediaStream` it was fanned out on -- reachable
+    /// when a peer with both publish and subscribe rights (e.g. over
+    /// `/ws/signal`) is auto-subscribed on join and then starts publishing,
+    /// replacing that subscriber entry with a publisher one.
+    ///
+    /// If the replaced session was a publisher, every subscriber fanned out
+    /// onto its now-dead `media_stream` is moved onto the new one instead --
+    /// otherwise they'd be left pointing at a cancelled stream the instant
+    /// their publisher renegotiates, the same way `close_session` would leave
+    /// them if it didn't call `remove_subscribers_of`. Their own
+    /// `CancellationToken`/`WebrtcTrack` are left alone; only the stream they
+    /// point at changes, so a subsequent `PeerSignal::Renegotiate` re-offers
+    /// the same track against the tracks the new stream carries.
+    pub async fn insert(&self, session_id: impl Into<String>, handle: SessionHandle) {
+        let session_id = session_id.into();
+        let new_media_stream = handle.media_stream.clone();
+        if let Some(previous) = self.sessions.insert(session_id.clone(), handle) {
+            if previous.role == SessionRole::Subscriber {
+                previous.media_stream.remove_subscriber_track(&session_id).await;
+            } else if previous.role == SessionRole::Publisher {
+                for (subscriber_id, mut subscriber) in self.remove_subscribers_of(&previous.media_stream) {
+                    previous.media_stream.remove_subscriber_track(&subscriber_id).await;
+                    new_media_stream
+                        .add_subscriber_track(subscriber_id.clone(), Box::new(subscriber.webrtc_track.clone()))
+                        .await;
+                    subscriber.media_stream = new_media_stream.clone();
+                    self.sessions.insert(subscriber_id, subscriber);
+                }
+            }
+            previous.cancel_token.cancel();
+        }
+    }
+
+    pub fn get(&self, session_id: &str) -> Option<SessionHandle> {
+        self.sessions.get(session_id).map(|entry| entry.clone())
+    }
+
+    /// Remove and return a session's handle, e.g. so its `CancellationToken`
+    /// can be cancelled by the caller as part of teardown.
+    pub fn remove(&self, session_id: &str) -> Option<SessionHandle> {
+        self.sessions.remove(session_id).map(|(_, handle)| handle)
+    }
+
+    /// Find the media stream a WHEP offer should subscribe to: the named
+    /// publisher session if one was requested, otherwise an arbitrary active
+    /// publisher.
+    pub fn find_publisher_stream(&self, session_id: Option<&str>) -> Option<Arc<MediaStream>> {
+        match session_id {
+            Some(id) => self
+                .sessions
+                .get(id)
+                .filter(|handle| handle.role == SessionRole::Publisher)
+                .map(|handle| handle.media_stream.clone()),
+            None => self
+                .sessions
+                .iter()
+                .find(|entry| entry.role == SessionRole::Publisher)
+                .map(|entry| entry.media_stream.clone()),
+        }
+    }
+
+    /// Tag a previously-inserted session with the signaling room it belongs
+    /// to, so `find_publisher_stream_in_room` can later route a subscriber in
+    /// that room to it. A no-op if the session has already been removed.
+    pub fn set_room(&self, session_id: &str, room: String) {
+        if let Some(mut entry) = self.sessions.get_mut(session_id) {
+            entry.room = Some(room);
+        }
+    }
+
+    /// Find the media stream of the active publisher in a given signaling
+    /// room, for `/ws/signal` subscribers.
+    pub fn find_publisher_stream_in_room(&self, room: &str) -> Option<Arc<MediaStream>> {
+        self.sessions
+            .iter()
+            .find(|entry| {
+                entry.role == SessionRole::Publisher && entry.room.as_deref() == Some(room)
+            })
+            .map(|entry| entry.media_stream.clone())
+    }
+
+    /// Remove and return every subscriber session fanned out onto
+    /// `media_stream`, e.g. because its publisher session is being closed and
+    /// the stream is going away with it. Without this, a departed publisher
+    /// leaves its viewers' `SessionHandle`s pointing at a cancelled stream
+    /// until `gc_expired` eventually reaps them on idle timeout.
+    pub fn remove_subscribers_of(&self, media_stream: &Arc<MediaStream>) -> Vec<(String, SessionHandle)> {
+        let subscriber_ids: Vec<String> = self
+            .sessions
+            .iter()
+            .filter(|entry| {
+                entry.role == SessionRole::Subscriber && Arc::ptr_eq(&entry.media_stream, media_stream)
+            })
+            .map(|entry| entry.key().clone())
+            .collect();
+
+        subscriber_ids
+            .into_iter()
+            .filter_map(|session_id| self.sessions.remove(&session_id))
+            .collect()
+    }
+
+    /// Drop sessions that have gone without signaling activity for longer
+    /// than `SESSION_IDLE_TTL` without being closed explicitly, unwinding each
+    /// one the same way an explicit close (`close_session`) would: a reaped
+    /// subscriber has its track detached from the publisher `MediaStream` it
+    /// was fanned out on, and a reaped publisher cascades the same detach and
+    /// cancellation to every subscriber fanned out onto its stream, so they
+    /// don't linger pointing at a cancelled stream either. See
+    /// `SESSION_IDLE_TTL` for what counts as activity.
+    ///
+    /// `DashMap::retain`'s closure can't itself be async (detaching a
+    /// subscriber track is), so this first collects the expired ids with a
+    /// synchronous pass, then removes and unwinds each one in turn.
+    pub async fn gc_expired(&self) {
+        let expired_ids: Vec<String> = self
+            .sessions
+            .iter()
+            .filter(|entry| entry.idle_for() >= SESSION_IDLE_TTL)
+            .map(|entry| entry.key().clone())
+            .collect();
+
+        for session_id in expired_ids {
+            // May already be gone if an explicit close raced this sweep.
+            let Some(handle) = self.remove(&session_id) else {
+                continue;
+            };
+            info!("Expiring idle WebRTC session: {}", session_id);
+
+            if handle.role == SessionRole::Subscriber {
+                handle.media_stream.remove_subscriber_track(&session_id).await;
+            } else {
+                for (subscriber_id, subscriber_handle) in self.remove_subscribers_of(&handle.media_stream) {
+                    subscriber_handle
+                        .media_stream
+                        .remove_subscriber_track(&subscriber_id)
+                        .await;
+                    subscriber_handle.cancel_token.cancel();
+                }
+            }
+            handle.cancel_token.cancel();
+        }
+    }
+
+    /// Spawn a background task that periodically sweeps expired sessions.
+    /// Intended to be called once, when the owning `AppState` is built.
+    pub fn spawn_gc(&self) {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(GC_INTERVAL);
+            loop {
+                interval.tick().await;
+                manager.gc_expired().await;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::create_event_sender;
+    use crate::media::{stream::MediaStreamBuilder, track::TrackConfig};
+
+    fn test_media_stream() -> Arc<MediaStream> {
+        Arc::new(MediaStreamBuilder::new(create_event_sender()).build())
+    }
+
+    fn test_handle(role: SessionRole, media_stream: Arc<MediaStream>) -> SessionHandle {
+        let cancel_token = CancellationToken::new();
+        let webrtc_track =
+            WebrtcTrack::new(cancel_token.child_token(), "test-track".to_string(), TrackConfig::default());
+        SessionHandle::new(role, cancel_token, media_stream, webrtc_track)
+    }
+
+    #[tokio::test]
+    async fn insert_cancels_the_session_it_replaces() {
+        let manager = SessionManager::new();
+        let stream = test_media_stream();
+
+        let original = test_handle(SessionRole::Publisher, stream.clone());
+        let original_token = original.cancel_token.clone();
+        manager.insert("peer-1", original).await;
+        assert!(!original_token.is_cancelled());
+
+        let replacement = test_handle(SessionRole::Publisher, stream);
+        manager.insert("peer-1", replacement).await;
+        assert!(original_token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn insert_replacing_a_publisher_rewires_its_subscribers_to_the_new_stream() {
+        // A publisher renegotiating (or just re-sending `Offer`) replaces its
+        // own session entry with a fresh `media_stream`. Its subscribers must
+        // follow onto the new stream rather than being left pointing at the
+        // one that's about to be cancelled. (`close_session` instead removes
+        // and cancels them via `remove_subscribers_of`.)
+        let manager = SessionManager::new();
+        let old_stream = test_media_stream();
+        let new_stream = test_media_stream();
+
+        manager
+            .insert("publisher-1", test_handle(SessionRole::Publisher, old_stream.clone()))
+            .await;
+        manager
+            .insert("viewer-1", test_handle(SessionRole::Subscriber, old_stream.clone()))
+            .await;
+
+        manager
+            .insert("publisher-1", test_handle(SessionRole::Publisher, new_stream.clone()))
+            .await;
+
+        let viewer = manager
+            .get("viewer-1")
+            .expect("a renegotiating publisher must not drop its subscribers");
+        assert!(Arc::ptr_eq(&viewer.media_stream, &new_stream));
+        assert!(!Arc::ptr_eq(&viewer.media_stream, &old_stream));
+    }
+
+    #[tokio::test]
+    async fn insert_cancels_a_replaced_subscriber_too() {
+        // Reachable for a peer with both publish and subscribe rights: joining
+        // auto-subscribes it under its own peer id, and if it then starts
+        // publishing, `insert` replaces that subscriber entry with a publisher
+        // one. The replaced subscriber's token must still be cancelled (its
+        // track detach from the publisher stream is not asserted here).
+        let manager = SessionManager::new();
+        let stream = test_media_stream();
+
+        let subscriber = test_handle(SessionRole::Subscriber, stream.clone());
+        let subscriber_token = subscriber.cancel_token.clone();
+        manager.insert("peer-1", subscriber).await;
+
+        let publisher = test_handle(SessionRole::Publisher, stream);
+        manager.insert("peer-1", publisher).await;
+
+        assert!(subscriber_token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn find_publisher_stream_in_room_only_matches_tagged_publisher() {
+        let manager = SessionManager::new();
+        let stream = test_media_stream();
+        manager
+            .insert("pub-1", test_handle(SessionRole::Publisher, stream.clone()))
+            .await;
+
+        assert!(manager.find_publisher_stream_in_room("room-a").is_none());
+
+        manager.set_room("pub-1", "room-a".to_string());
+        assert!(manager.find_publisher_stream_in_room("room-a").is_some());
+        assert!(manager.find_publisher_stream_in_room("room-b").is_none());
+    }
+
+    #[tokio::test]
+    async fn remove_subscribers_of_only_removes_sessions_on_that_stream() {
+        let manager = SessionManager::new();
+        let stream_a = test_media_stream();
+        let stream_b = test_media_stream();
+
+        manager
+            .insert("sub-a1", test_handle(SessionRole::Subscriber, stream_a.clone()))
+            .await;
+        manager
+            .insert("sub-a2", test_handle(SessionRole::Subscriber, stream_a.clone()))
+            .await;
+        manager
+            .insert("sub-b1", test_handle(SessionRole::Subscriber, stream_b.clone()))
+            .await;
+
+        let removed = manager.remove_subscribers_of(&stream_a);
+        let mut removed_ids: Vec<&str> = removed.iter().map(|(id, _)| id.as_str()).collect();
+        removed_ids.sort_unstable();
+        assert_eq!(removed_ids, ["sub-a1", "sub-a2"]);
+
+        assert!(manager.get("sub-a1").is_none());
+        assert!(manager.get("sub-a2").is_none());
+        assert!(manager.get("sub-b1").is_some());
+    }
+
+    #[tokio::test]
+    async fn gc_expired_evicts_only_idle_sessions() {
+        let manager = SessionManager::new();
+        let stream = test_media_stream();
+
+        let idle = test_handle(SessionRole::Publisher, stream.clone());
+        let idle_token = idle.cancel_token.clone();
+        // Backdate past SESSION_IDLE_TTL instead of waiting hours for real time to pass.
+        idle.last_activity.store(
+            unix_now_secs().saturating_sub(SESSION_IDLE_TTL.as_secs() + 1),
+            Ordering::Relaxed,
+        );
+        manager.insert("idle-peer", idle).await;
+
+        let fresh = test_handle(SessionRole::Publisher, stream);
+        manager.insert("fresh-peer", fresh).await;
+
+        manager.gc_expired().await;
+
+        assert!(manager.get("idle-peer").is_none());
+        assert!(idle_token.is_cancelled());
+        assert!(manager.get("fresh-peer").is_some());
+    }
+
+    #[tokio::test]
+    async fn gc_expired_cascades_to_an_idle_publishers_subscribers() {
+        // An idle-reaped publisher must unwind exactly like `close_session`
+        // does: its subscribers are detached and cancelled too, rather than
+        // left pointing at the stream that's about to be cancelled.
+        let manager = SessionManager::new();
+        let stream = test_media_stream();
+
+        let publisher = test_handle(SessionRole::Publisher, stream.clone());
+        publisher.last_activity.store(
+            unix_now_secs().saturating_sub(SESSION_IDLE_TTL.as_secs() + 1),
+            Ordering::Relaxed,
+        );
+        manager.insert("publisher-1", publisher).await;
+
+        let viewer = test_handle(SessionRole::Subscriber, stream);
+        let viewer_token = viewer.cancel_token.clone();
+        manager.insert("viewer-1", viewer).await;
+
+        manager.gc_expired().await;
+
+        assert!(manager.get("publisher-1").is_none());
+        assert!(manager.get("viewer-1").is_none());
+        assert!(viewer_token.is_cancelled());
+    }
+}